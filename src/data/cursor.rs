@@ -0,0 +1,155 @@
+use std::vec;
+
+use rocket::serde::{Deserialize, Serialize};
+
+use super::entity::{Entity, VersionedEntity};
+use super::error::RepositoryError;
+use super::repository::Repository;
+use super::serializer::Serializer;
+use super::storage::Storage;
+
+/// A lazy cursor over a [`Repository`]'s entities, deserializing each entity only as the
+/// cursor is advanced rather than up front.
+pub struct RepositoryCursor<'a, T, S, R>
+where
+    T: Entity,
+    S: Storage,
+{
+    repository: &'a Repository<T, S, R>,
+    keys: vec::IntoIter<Vec<u8>>,
+    include_deleted: bool,
+}
+
+impl<'a, T, S, R> RepositoryCursor<'a, T, S, R>
+where
+    T: Entity + Serialize + for<'de> Deserialize<'de>,
+    S: Storage,
+    R: Serializer,
+{
+    pub(super) fn new(repository: &'a Repository<T, S, R>, keys: Vec<Vec<u8>>, include_deleted: bool) -> Self {
+        Self {
+            repository,
+            keys: keys.into_iter(),
+            include_deleted,
+        }
+    }
+
+    /// Advances the cursor, returning the next entity, a repository error, or `None` once
+    /// the cursor is exhausted. Tombstoned entities are skipped unless `include_deleted` was set
+    /// when the cursor was opened.
+    pub async fn advance(&mut self) -> Option<Result<VersionedEntity<T>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>>> {
+        loop {
+            let key = self.keys.next()?;
+
+            match self.repository.storage.read(&key).await {
+                Ok(Some(bytes)) => match Repository::<T, S, R>::decode_envelope(&bytes) {
+                    Ok(versioned) if versioned.deleted && !self.include_deleted => continue,
+                    result => return Some(result),
+                },
+                Ok(None) => continue,
+                Err(err) => return Some(Err(RepositoryError::Backend(err))),
+            }
+        }
+    }
+
+    /// Drains the remainder of this cursor into a `Vec`, restoring the eager `get_many` behavior.
+    pub async fn collect(mut self) -> Result<Vec<VersionedEntity<T>>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let mut entities = Vec::new();
+
+        while let Some(entity) = self.advance().await {
+            entities.push(entity?);
+        }
+
+        Ok(entities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+
+    use super::super::storage::MemoryStorage;
+    use super::*;
+
+    /// A [`Storage`] wrapper that counts how many times `read` is called, to prove a
+    /// [`RepositoryCursor`] decodes entities as it's advanced rather than up front.
+    #[derive(Default)]
+    struct CountingStorage {
+        inner: MemoryStorage,
+        reads: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Storage for CountingStorage {
+        type ErrType = <MemoryStorage as Storage>::ErrType;
+
+        async fn read(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::ErrType> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.read(key).await
+        }
+
+        async fn write(&self, key: &[u8], value: &[u8]) -> Result<(), Self::ErrType> {
+            self.inner.write(key, value).await
+        }
+
+        async fn remove(&self, key: &[u8]) -> Result<(), Self::ErrType> {
+            self.inner.remove(key).await
+        }
+
+        async fn list_keys(&self) -> Result<Vec<Vec<u8>>, Self::ErrType> {
+            self.inner.list_keys().await
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Note {
+        id: u64,
+        body: String,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(crate = "rocket::serde")]
+    struct NoteInput {
+        id: u64,
+        body: String,
+    }
+
+    impl From<NoteInput> for Note {
+        fn from(input: NoteInput) -> Self {
+            Self {
+                id: input.id,
+                body: input.body,
+            }
+        }
+    }
+
+    impl Entity for Note {
+        type PrimaryKey = u64;
+        type Input = NoteInput;
+
+        fn get_pk(&self) -> Self::PrimaryKey {
+            self.id
+        }
+
+        const PK_DEFAULT: Self::PrimaryKey = 0;
+    }
+
+    #[rocket::async_test]
+    async fn advance_only_reads_as_many_entities_as_are_consumed() {
+        let repo: Repository<Note, CountingStorage> = Repository::new(CountingStorage::default());
+
+        for id in 1..=5 {
+            repo.create(NoteInput { id, body: "note".to_string() }).await.unwrap();
+        }
+
+        let mut cursor = repo.find_all(false).await.unwrap();
+        // `create` reads each key once to check for an existing entity, so count from here.
+        let reads_before = cursor.repository.storage.reads.load(Ordering::SeqCst);
+        cursor.advance().await.unwrap().unwrap();
+
+        assert_eq!(cursor.repository.storage.reads.load(Ordering::SeqCst) - reads_before, 1);
+    }
+}
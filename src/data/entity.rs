@@ -0,0 +1,70 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
+use rocket::serde::{Serialize, Deserialize};
+
+use super::key::KeyEncoding;
+
+/// An abstract represention of a data entity uniquely identifiable by a primary key.
+///
+/// `PrimaryKey` now also requires `Clone` and `KeyEncoding`, and `Input` is new - see each
+/// field's doc comment below.
+pub trait Entity {
+    /// The type of the primary key for this entity. `KeyEncoding` is required so the repository
+    /// can store and scan entities in byte-ordered form, rather than in whatever order the
+    /// chosen `Serializer` happens to produce.
+    type PrimaryKey: Serialize + for<'a> Deserialize<'a> + Eq + Display + Clone + KeyEncoding;
+
+    /// The type of input object to use for entity creation and modification.
+    type Input: Into<Self>;
+
+    /// Gets the primary key of this entity.
+    fn get_pk(&self) -> Self::PrimaryKey;
+
+    /// The default primary key for when none is explicitly assigned.
+    const PK_DEFAULT: Self::PrimaryKey;
+}
+
+/// A per-entity revision token, combining a monotonically increasing counter with a content
+/// hash, used to detect lost updates under optimistic concurrency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+pub struct Revision {
+    counter: u64,
+    hash: u64,
+}
+
+impl Revision {
+    /// The revision assigned the first time an entity is written.
+    pub(super) fn initial(entity_bytes: &[u8]) -> Self {
+        Self {
+            counter: 0,
+            hash: Self::hash_bytes(entity_bytes),
+        }
+    }
+
+    /// The revision that follows this one, given the newly-written entity bytes.
+    pub(super) fn next(&self, entity_bytes: &[u8]) -> Self {
+        Self {
+            counter: self.counter + 1,
+            hash: Self::hash_bytes(entity_bytes),
+        }
+    }
+
+    fn hash_bytes(bytes: &[u8]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// An entity paired with the revision it was read or written at, letting callers perform a
+/// subsequent compare-and-swap `update`/`delete` without a separate lookup.
+#[derive(Debug, Clone)]
+pub struct VersionedEntity<T: Entity> {
+    pub entity: T,
+    pub revision: Revision,
+    /// Whether this entity is a tombstone, i.e. has been soft-deleted.
+    pub deleted: bool,
+}
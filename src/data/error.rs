@@ -0,0 +1,41 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+
+/// Errors that can occur while performing a [`Repository`](crate::data::repository::Repository)
+/// operation, parameterized over the entity's primary key type and the backend's storage and
+/// serialization error types.
+#[derive(Debug)]
+pub enum RepositoryError<PK, SErr, DErr> {
+    /// No entity exists for the given primary key.
+    NotFound(PK),
+    /// An entity already exists for the given primary key.
+    AlreadyExists(PK),
+    /// The caller's expected revision did not match the entity's stored revision.
+    Conflict,
+    /// Encoding or decoding an entity failed.
+    Serialization(DErr),
+    /// The underlying storage backend failed.
+    Backend(SErr),
+}
+
+impl<PK: Display, SErr: Display, DErr: Display> Display for RepositoryError<PK, SErr, DErr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(pk) => write!(f, "no entity found for primary key {pk}"),
+            Self::AlreadyExists(pk) => write!(f, "an entity already exists for primary key {pk}"),
+            Self::Conflict => write!(f, "entity revision does not match the expected revision"),
+            Self::Serialization(err) => write!(f, "serialization error: {err}"),
+            Self::Backend(err) => write!(f, "storage error: {err}"),
+        }
+    }
+}
+
+impl<PK: Debug + Display, SErr: Error + 'static, DErr: Error + 'static> Error for RepositoryError<PK, SErr, DErr> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NotFound(_) | Self::AlreadyExists(_) | Self::Conflict => None,
+            Self::Serialization(err) => Some(err),
+            Self::Backend(err) => Some(err),
+        }
+    }
+}
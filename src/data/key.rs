@@ -0,0 +1,110 @@
+/// Produces a byte-ordered, memcmp-comparable encoding of a primary key, so storage backends
+/// can perform range scans and keyset pagination by comparing raw bytes instead of decoding
+/// each candidate key.
+pub trait KeyEncoding {
+    /// Encodes this value such that lexicographically comparing the resulting bytes yields the
+    /// same ordering as comparing the original values.
+    fn encode_ordered(&self) -> Vec<u8>;
+}
+
+macro_rules! impl_key_encoding_uint {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl KeyEncoding for $t {
+                fn encode_ordered(&self) -> Vec<u8> {
+                    self.to_be_bytes().to_vec()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_key_encoding_int {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl KeyEncoding for $t {
+                fn encode_ordered(&self) -> Vec<u8> {
+                    // Flipping the sign bit maps two's-complement ordering onto unsigned byte ordering.
+                    let mut bytes = self.to_be_bytes();
+                    bytes[0] ^= 0x80;
+                    bytes.to_vec()
+                }
+            }
+        )*
+    };
+}
+
+impl_key_encoding_uint!(u8, u16, u32, u64, u128, usize);
+impl_key_encoding_int!(i8, i16, i32, i64, i128, isize);
+
+impl KeyEncoding for String {
+    fn encode_ordered(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+/// Turns a key prefix into an inclusive-start/exclusive-end byte range covering every key that
+/// begins with that prefix.
+pub trait IntoPrefixRange {
+    /// Returns `(start, end)`, where `start` is the prefix itself and `end` is the prefix with
+    /// its last byte incremented, carrying into preceding bytes as needed. `end` is `None` when
+    /// the prefix is all `0xFF`, meaning the range is unbounded above.
+    fn into_prefix_range(self) -> (Vec<u8>, Option<Vec<u8>>);
+}
+
+impl IntoPrefixRange for Vec<u8> {
+    fn into_prefix_range(self) -> (Vec<u8>, Option<Vec<u8>>) {
+        let mut end = self.clone();
+
+        for byte in end.iter_mut().rev() {
+            if *byte == 0xFF {
+                *byte = 0x00;
+                continue;
+            }
+
+            *byte += 1;
+            return (self, Some(end));
+        }
+
+        (self, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_range_increments_last_byte() {
+        let (start, end) = vec![0x01, 0x02].into_prefix_range();
+
+        assert_eq!(start, vec![0x01, 0x02]);
+        assert_eq!(end, Some(vec![0x01, 0x03]));
+    }
+
+    #[test]
+    fn prefix_range_carries_across_trailing_0xff() {
+        let (start, end) = vec![0x01, 0xFF, 0xFF].into_prefix_range();
+
+        assert_eq!(start, vec![0x01, 0xFF, 0xFF]);
+        assert_eq!(end, Some(vec![0x02, 0x00, 0x00]));
+    }
+
+    #[test]
+    fn prefix_range_is_unbounded_for_all_0xff() {
+        let (start, end) = vec![0xFF, 0xFF].into_prefix_range();
+
+        assert_eq!(start, vec![0xFF, 0xFF]);
+        assert_eq!(end, None);
+    }
+
+    #[test]
+    fn uint_key_encoding_preserves_numeric_order() {
+        assert!(5u64.encode_ordered() < 100u64.encode_ordered());
+    }
+
+    #[test]
+    fn signed_key_encoding_preserves_numeric_order_across_zero() {
+        assert!((-1i32).encode_ordered() < 1i32.encode_ordered());
+    }
+}
@@ -0,0 +1,222 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use async_trait::async_trait;
+
+use super::storage::Storage;
+
+/// The reserved storage key under which the currently-applied migration version is recorded.
+///
+/// This key is written directly into the same [`Storage`] a [`Repository`](super::repository::Repository)
+/// scans, so its first byte must never collide with `Repository::ENTITY_KEY_PREFIX` (`0x01`) -
+/// otherwise it would surface as a corrupt entity from `find_all`/`get_many`/`merge`/the range
+/// scans. `b'_'` (`0x5F`) keeps it well clear of that prefix.
+const VERSION_KEY: &[u8] = b"__migrations_version__";
+
+/// A single reversible change to a [`Storage`] backend's persisted data.
+#[async_trait]
+pub trait Migration<S: Storage>: Send + Sync {
+    /// Applies this migration.
+    async fn up(&self, storage: &S) -> Result<(), S::ErrType>;
+
+    /// Reverts this migration.
+    async fn down(&self, storage: &S) -> Result<(), S::ErrType>;
+}
+
+/// Errors that can occur while running migrations.
+#[derive(Debug)]
+pub enum MigrationError<SErr> {
+    /// The stored version, or the requested target version, doesn't correspond to any known
+    /// migration step.
+    UnknownVersion(u64),
+    /// The recorded version metadata could not be parsed.
+    CorruptVersion,
+    /// The underlying storage backend failed.
+    Storage(SErr),
+}
+
+impl<SErr: Display> Display for MigrationError<SErr> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownVersion(version) => write!(f, "migration version {version} is not known to this migration set"),
+            Self::CorruptVersion => write!(f, "stored migration version metadata could not be parsed"),
+            Self::Storage(err) => write!(f, "storage error: {err}"),
+        }
+    }
+}
+
+impl<SErr: Error + 'static> Error for MigrationError<SErr> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnknownVersion(_) | Self::CorruptVersion => None,
+            Self::Storage(err) => Some(err),
+        }
+    }
+}
+
+/// An ordered collection of [`Migration`]s, applied or rolled back sequentially against a
+/// [`Storage`] backend. The version applied so far is recorded under a reserved metadata key in
+/// that same storage, mirroring the `rusqlite-migration` workflow.
+pub struct Migrations<S: Storage> {
+    steps: Vec<Box<dyn Migration<S>>>,
+}
+
+impl<S: Storage> Migrations<S> {
+    /// Starts an empty migration set.
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Appends a migration step to the end of this set.
+    pub fn add(mut self, migration: impl Migration<S> + 'static) -> Self {
+        self.steps.push(Box::new(migration));
+        self
+    }
+
+    /// The version number once every migration in this set has been applied.
+    pub fn latest_version(&self) -> u64 {
+        self.steps.len() as u64
+    }
+
+    /// Applies every pending migration in order, bringing `storage` to the latest version.
+    pub async fn to_latest(&self, storage: &S) -> Result<u64, MigrationError<S::ErrType>> {
+        self.to_version(storage, self.latest_version()).await
+    }
+
+    /// Applies or reverts migrations as needed to bring `storage` to exactly `target_version`.
+    pub async fn to_version(&self, storage: &S, target_version: u64) -> Result<u64, MigrationError<S::ErrType>> {
+        let current_version = self.current_version(storage).await?;
+
+        if current_version > self.latest_version() {
+            return Err(MigrationError::UnknownVersion(current_version));
+        }
+
+        if target_version > self.latest_version() {
+            return Err(MigrationError::UnknownVersion(target_version));
+        }
+
+        match target_version.cmp(&current_version) {
+            Ordering::Greater => {
+                for step in &self.steps[current_version as usize..target_version as usize] {
+                    step.up(storage).await.map_err(MigrationError::Storage)?;
+                }
+            }
+            Ordering::Less => {
+                for step in self.steps[target_version as usize..current_version as usize].iter().rev() {
+                    step.down(storage).await.map_err(MigrationError::Storage)?;
+                }
+            }
+            Ordering::Equal => {}
+        }
+
+        self.record_version(storage, target_version).await?;
+
+        Ok(target_version)
+    }
+
+    async fn current_version(&self, storage: &S) -> Result<u64, MigrationError<S::ErrType>> {
+        match storage.read(VERSION_KEY).await.map_err(MigrationError::Storage)? {
+            Some(bytes) => {
+                let bytes: [u8; 8] = bytes.try_into().map_err(|_| MigrationError::CorruptVersion)?;
+
+                Ok(u64::from_be_bytes(bytes))
+            }
+            None => Ok(0),
+        }
+    }
+
+    async fn record_version(&self, storage: &S, version: u64) -> Result<(), MigrationError<S::ErrType>> {
+        storage.write(VERSION_KEY, &version.to_be_bytes()).await.map_err(MigrationError::Storage)
+    }
+}
+
+impl<S: Storage> Default for Migrations<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::storage::MemoryStorage;
+    use super::*;
+
+    /// A migration that writes `key` on `up` and removes it on `down`, so tests can observe
+    /// which steps have actually run.
+    struct WriteKey {
+        key: &'static [u8],
+    }
+
+    #[async_trait]
+    impl Migration<MemoryStorage> for WriteKey {
+        async fn up(&self, storage: &MemoryStorage) -> Result<(), <MemoryStorage as Storage>::ErrType> {
+            storage.write(self.key, b"applied").await
+        }
+
+        async fn down(&self, storage: &MemoryStorage) -> Result<(), <MemoryStorage as Storage>::ErrType> {
+            storage.remove(self.key).await
+        }
+    }
+
+    fn migrations() -> Migrations<MemoryStorage> {
+        Migrations::new()
+            .add(WriteKey { key: b"step_1" })
+            .add(WriteKey { key: b"step_2" })
+    }
+
+    #[rocket::async_test]
+    async fn to_latest_applies_every_step_in_order() {
+        let storage = MemoryStorage::default();
+        let version = migrations().to_latest(&storage).await.unwrap();
+
+        assert_eq!(version, 2);
+        assert!(storage.read(b"step_1").await.unwrap().is_some());
+        assert!(storage.read(b"step_2").await.unwrap().is_some());
+    }
+
+    #[rocket::async_test]
+    async fn to_version_reverts_steps_above_the_target() {
+        let storage = MemoryStorage::default();
+        let steps = migrations();
+
+        steps.to_latest(&storage).await.unwrap();
+        let version = steps.to_version(&storage, 1).await.unwrap();
+
+        assert_eq!(version, 1);
+        assert!(storage.read(b"step_1").await.unwrap().is_some());
+        assert!(storage.read(b"step_2").await.unwrap().is_none());
+    }
+
+    #[rocket::async_test]
+    async fn to_version_is_idempotent_at_the_current_version() {
+        let storage = MemoryStorage::default();
+        let steps = migrations();
+
+        steps.to_latest(&storage).await.unwrap();
+        let version = steps.to_version(&storage, steps.latest_version()).await.unwrap();
+
+        assert_eq!(version, 2);
+    }
+
+    #[rocket::async_test]
+    async fn to_version_rejects_an_unknown_target_version() {
+        let storage = MemoryStorage::default();
+        let steps = migrations();
+
+        let err = steps.to_version(&storage, 99).await.unwrap_err();
+
+        assert!(matches!(err, MigrationError::UnknownVersion(99)));
+    }
+
+    #[rocket::async_test]
+    async fn to_latest_rejects_a_stored_version_newer_than_any_known_step() {
+        let storage = MemoryStorage::default();
+
+        storage.write(VERSION_KEY, &99u64.to_be_bytes()).await.unwrap();
+
+        let err = migrations().to_latest(&storage).await.unwrap_err();
+
+        assert!(matches!(err, MigrationError::UnknownVersion(99)));
+    }
+}
@@ -0,0 +1,817 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rocket::serde::{Deserialize, Serialize};
+
+use super::cursor::RepositoryCursor;
+use super::entity::{Entity, Revision, VersionedEntity};
+use super::error::RepositoryError;
+use super::key::{IntoPrefixRange, KeyEncoding};
+use super::serializer::{JsonSerializer, Serializer};
+use super::storage::Storage;
+use super::transaction::{Operation, OperationResult, Transaction};
+
+/// The on-disk representation of a stored entity: its data, the revision it was written at (for
+/// compare-and-swap), a timestamp (for last-write-wins merge), and a tombstone flag (for soft
+/// deletes).
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct Envelope<T> {
+    revision: Revision,
+    timestamp: u64,
+    deleted: bool,
+    entity: T,
+}
+
+/// A borrowing counterpart to [`Envelope`], used to encode a new envelope without having to
+/// give up ownership of the entity being written.
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct EnvelopeRef<'a, T> {
+    revision: Revision,
+    timestamp: u64,
+    deleted: bool,
+    entity: &'a T,
+}
+
+/// A backing store-agnostic data repository for a given [`Entity`] type, parameterized
+/// over a pluggable [`Storage`] backend and [`Serializer`] encoding.
+pub struct Repository<T, S, R = JsonSerializer>
+where
+    T: Entity,
+    S: Storage,
+{
+    pub(super) storage: S,
+    _marker: PhantomData<(T, R)>,
+}
+
+impl<T, S, R> Repository<T, S, R>
+where
+    T: Entity + Serialize + for<'a> Deserialize<'a>,
+    S: Storage,
+    R: Serializer,
+{
+    /// Creates a new repository backed by the given storage.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The leading byte every entity key is stored under, keeping the entity keyspace disjoint
+    /// from reserved metadata keys (e.g. [`Migrations`](super::migration::Migrations)'s applied
+    /// version counter) that may share the same [`Storage`] backend. Reserved keys must avoid
+    /// this prefix so they never surface from `find_all`/`get_many`/`merge`/the range scans.
+    const ENTITY_KEY_PREFIX: u8 = 0x01;
+
+    /// Encodes a primary key into its byte-ordered storage key, via [`KeyEncoding`] rather than
+    /// `R`'s serializer, so that `get_range`/`get_page`/`get_by_prefix` scan the same keyspace
+    /// that point operations write into. Prefixed with [`Self::ENTITY_KEY_PREFIX`].
+    fn key_for(pk: &T::PrimaryKey) -> Vec<u8> {
+        let mut key = vec![Self::ENTITY_KEY_PREFIX];
+        key.extend(pk.encode_ordered());
+        key
+    }
+
+    /// The inclusive-start/exclusive-end byte range spanning every possible entity key.
+    fn entity_key_range() -> (Vec<u8>, Option<Vec<u8>>) {
+        vec![Self::ENTITY_KEY_PREFIX].into_prefix_range()
+    }
+
+    fn decode_raw_envelope(bytes: &[u8]) -> Result<Envelope<T>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        R::decode(bytes).map_err(RepositoryError::Serialization)
+    }
+
+    pub(super) fn decode_envelope(bytes: &[u8]) -> Result<VersionedEntity<T>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let envelope = Self::decode_raw_envelope(bytes)?;
+
+        Ok(VersionedEntity {
+            entity: envelope.entity,
+            revision: envelope.revision,
+            deleted: envelope.deleted,
+        })
+    }
+
+    /// Encodes an envelope wrapping `entity`, without taking ownership of it.
+    fn encode_envelope(entity: &T, revision: Revision, timestamp: u64, deleted: bool) -> Result<Vec<u8>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        R::encode(&EnvelopeRef {
+            revision,
+            timestamp,
+            deleted,
+            entity,
+        })
+        .map_err(RepositoryError::Serialization)
+    }
+
+    fn now_millis() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+    }
+
+    /// Opens a lazy cursor over every entity in this repository, deserializing each entity only
+    /// as the cursor is advanced. Tombstoned entities are skipped unless `include_deleted` is set.
+    pub async fn find_all(&self, include_deleted: bool) -> Result<RepositoryCursor<'_, T, S, R>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let (start, end) = Self::entity_key_range();
+        let keys = self
+            .storage
+            .scan_keys(Some(&start), end.as_deref(), None)
+            .await
+            .map_err(RepositoryError::Backend)?;
+
+        Ok(RepositoryCursor::new(self, keys, include_deleted))
+    }
+
+    /// Retrieve all entities available in this repository, hiding tombstones unless
+    /// `include_deleted` is set.
+    ///
+    /// This is a convenience wrapper around [`Repository::find_all`] that eagerly collects
+    /// the cursor; prefer `find_all` when only some of the entities are needed.
+    pub async fn get_many(&self, include_deleted: bool) -> Result<Vec<VersionedEntity<T>>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        self.find_all(include_deleted).await?.collect().await
+    }
+
+    /// Attempt to retrieve a single entity by its primary key. A tombstoned entity is reported
+    /// as not found.
+    pub async fn get_by_pk(&self, pk: &T::PrimaryKey) -> Result<VersionedEntity<T>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let versioned = self.read_envelope(pk).await?;
+
+        if versioned.deleted {
+            return Err(RepositoryError::NotFound(pk.clone()));
+        }
+
+        Ok(versioned)
+    }
+
+    /// Creates a new entity with the given input data. Fails with
+    /// [`RepositoryError::AlreadyExists`] if a non-tombstoned entity already exists for the
+    /// entity's primary key.
+    pub async fn create(&self, input: T::Input) -> Result<VersionedEntity<T>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let entity: T = input.into();
+        let pk = entity.get_pk();
+        let key = Self::key_for(&pk);
+
+        if let Some(existing_bytes) = self.storage.read(&key).await.map_err(RepositoryError::Backend)? {
+            if !Self::decode_raw_envelope(&existing_bytes)?.deleted {
+                return Err(RepositoryError::AlreadyExists(pk));
+            }
+        }
+
+        let entity_bytes = R::encode(&entity).map_err(RepositoryError::Serialization)?;
+        let revision = Revision::initial(&entity_bytes);
+        let envelope_bytes = Self::encode_envelope(&entity, revision, Self::now_millis(), false)?;
+
+        self.storage.write(&key, &envelope_bytes).await.map_err(RepositoryError::Backend)?;
+
+        Ok(VersionedEntity {
+            entity,
+            revision,
+            deleted: false,
+        })
+    }
+
+    /// Updates the entity with the given primary key with the given input data, provided
+    /// `expected_revision` matches the entity's current revision; otherwise returns
+    /// [`RepositoryError::Conflict`]. Updating a tombstoned entity resurrects it.
+    pub async fn update(
+        &self,
+        pk: &T::PrimaryKey,
+        expected_revision: Revision,
+        updated_input: T::Input,
+    ) -> Result<Revision, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let current = self.read_envelope(pk).await?;
+
+        if current.revision != expected_revision {
+            return Err(RepositoryError::Conflict);
+        }
+
+        let key = Self::key_for(pk);
+        let entity: T = updated_input.into();
+        let entity_bytes = R::encode(&entity).map_err(RepositoryError::Serialization)?;
+        let revision = current.revision.next(&entity_bytes);
+        let envelope_bytes = Self::encode_envelope(&entity, revision, Self::now_millis(), false)?;
+
+        self.storage.write(&key, &envelope_bytes).await.map_err(RepositoryError::Backend)?;
+
+        Ok(revision)
+    }
+
+    /// Soft-deletes the entity with the given primary key, provided `expected_revision` matches
+    /// the entity's current revision; otherwise returns [`RepositoryError::Conflict`]. The
+    /// entity's data is retained as a tombstone rather than erased, so [`Repository::merge`] can
+    /// still reconcile it against other replicas.
+    pub async fn delete(&self, pk: &T::PrimaryKey, expected_revision: Revision) -> Result<(), RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let current = self.read_envelope(pk).await?;
+
+        if current.revision != expected_revision {
+            return Err(RepositoryError::Conflict);
+        }
+
+        let key = Self::key_for(pk);
+        let entity_bytes = R::encode(&current.entity).map_err(RepositoryError::Serialization)?;
+        let revision = current.revision.next(&entity_bytes);
+        let envelope_bytes = Self::encode_envelope(&current.entity, revision, Self::now_millis(), true)?;
+
+        self.storage.write(&key, &envelope_bytes).await.map_err(RepositoryError::Backend)
+    }
+
+    async fn read_envelope(&self, pk: &T::PrimaryKey) -> Result<VersionedEntity<T>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let key = Self::key_for(pk);
+        let bytes = self
+            .storage
+            .read(&key)
+            .await
+            .map_err(RepositoryError::Backend)?
+            .ok_or_else(|| RepositoryError::NotFound(pk.clone()))?;
+
+        Self::decode_envelope(&bytes)
+    }
+
+    /// Reconciles this repository with `other`, keeping, for each primary key present in either
+    /// store, whichever version has the greater timestamp (last-write-wins), with a tombstone
+    /// winning ties. This is a CRDT-style merge, suitable for reconciling independently-written
+    /// replicas back into an eventually-consistent state.
+    pub async fn merge(&self, other: &Repository<T, S, R>) -> Result<(), RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let (start, end) = Self::entity_key_range();
+        let their_keys = other
+            .storage
+            .scan_keys(Some(&start), end.as_deref(), None)
+            .await
+            .map_err(RepositoryError::Backend)?;
+
+        for key in their_keys {
+            let Some(their_bytes) = other.storage.read(&key).await.map_err(RepositoryError::Backend)? else {
+                continue;
+            };
+            let their_envelope = Self::decode_raw_envelope(&their_bytes)?;
+
+            let winner_bytes = match self.storage.read(&key).await.map_err(RepositoryError::Backend)? {
+                None => their_bytes,
+                Some(our_bytes) => {
+                    let our_envelope = Self::decode_raw_envelope(&our_bytes)?;
+
+                    if Self::wins(&their_envelope, &our_envelope) {
+                        their_bytes
+                    } else {
+                        our_bytes
+                    }
+                }
+            };
+
+            self.storage.write(&key, &winner_bytes).await.map_err(RepositoryError::Backend)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether `candidate` should win over `incumbent`: a strictly greater timestamp
+    /// wins outright, and a tombstone wins a tie.
+    fn wins(candidate: &Envelope<T>, incumbent: &Envelope<T>) -> bool {
+        match candidate.timestamp.cmp(&incumbent.timestamp) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => candidate.deleted && !incumbent.deleted,
+        }
+    }
+
+    /// Retrieves every entity whose primary key falls within `[start, end)` (`end` exclusive),
+    /// in ascending key order, up to `limit` entities if given. Tombstoned entities are hidden
+    /// unless `include_deleted` is set, without shrinking a capped page below `limit` live
+    /// entities while more remain further on - see [`Repository::scan_live`].
+    pub async fn get_range(
+        &self,
+        start: Option<&T::PrimaryKey>,
+        end: Option<&T::PrimaryKey>,
+        limit: Option<usize>,
+        include_deleted: bool,
+    ) -> Result<Vec<VersionedEntity<T>>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let (range_start, range_end) = Self::entity_key_range();
+        let start = start.map_or(range_start, Self::key_for);
+        let end = end.map_or(range_end, |pk| Some(Self::key_for(pk)));
+
+        self.scan_live(start, end, limit, include_deleted).await
+    }
+
+    /// Keyset pagination: returns up to `limit` entities whose primary key sorts after
+    /// `after_pk`, or the first page if `after_pk` is `None`. Tombstoned entities are hidden
+    /// unless `include_deleted` is set, without shrinking the page below `limit` live entities
+    /// while more remain further on - see [`Repository::scan_live`].
+    pub async fn get_page(
+        &self,
+        after_pk: Option<&T::PrimaryKey>,
+        limit: usize,
+        include_deleted: bool,
+    ) -> Result<Vec<VersionedEntity<T>>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let (range_start, range_end) = Self::entity_key_range();
+        let start = after_pk.map_or(range_start, |pk| {
+            let mut bound = Self::key_for(pk);
+            bound.push(0x00);
+            bound
+        });
+
+        self.scan_live(start, range_end, Some(limit), include_deleted).await
+    }
+
+    /// Retrieves every entity whose encoded primary key starts with `prefix`, up to `limit`
+    /// entities if given. Tombstoned entities are hidden unless `include_deleted` is set,
+    /// without shrinking a capped page below `limit` live entities while more remain further on
+    /// - see [`Repository::scan_live`].
+    pub async fn get_by_prefix(
+        &self,
+        prefix: Vec<u8>,
+        limit: Option<usize>,
+        include_deleted: bool,
+    ) -> Result<Vec<VersionedEntity<T>>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let mut full_prefix = vec![Self::ENTITY_KEY_PREFIX];
+        full_prefix.extend(prefix);
+        let (start, end) = full_prefix.into_prefix_range();
+
+        self.scan_live(start, end, limit, include_deleted).await
+    }
+
+    /// Scans `[start, end)` for up to `limit` live entities (tombstones hidden unless
+    /// `include_deleted`), re-querying storage in further windows past the last key examined as
+    /// needed, so a tombstone landing inside the scanned range doesn't shrink a capped page below
+    /// `limit` live entities while more remain further on. `limit: None` scans the whole range in
+    /// one pass.
+    async fn scan_live(
+        &self,
+        mut start: Vec<u8>,
+        end: Option<Vec<u8>>,
+        limit: Option<usize>,
+        include_deleted: bool,
+    ) -> Result<Vec<VersionedEntity<T>>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let Some(limit) = limit else {
+            let keys = self
+                .storage
+                .scan_keys(Some(&start), end.as_deref(), None)
+                .await
+                .map_err(RepositoryError::Backend)?;
+
+            return self.decode_keys(keys, include_deleted).await;
+        };
+
+        let mut entities = Vec::with_capacity(limit);
+
+        loop {
+            let batch = self
+                .storage
+                .scan_keys(Some(&start), end.as_deref(), Some(limit))
+                .await
+                .map_err(RepositoryError::Backend)?;
+            let batch_len = batch.len();
+
+            let Some(last_key) = batch.last().cloned() else {
+                break;
+            };
+
+            for key in batch {
+                let Some(bytes) = self.storage.read(&key).await.map_err(RepositoryError::Backend)? else {
+                    continue;
+                };
+                let versioned = Self::decode_envelope(&bytes)?;
+
+                if versioned.deleted && !include_deleted {
+                    continue;
+                }
+
+                entities.push(versioned);
+
+                if entities.len() == limit {
+                    return Ok(entities);
+                }
+            }
+
+            if batch_len < limit {
+                break;
+            }
+
+            start = last_key;
+            start.push(0x00);
+        }
+
+        Ok(entities)
+    }
+
+    pub(super) async fn decode_keys(
+        &self,
+        keys: Vec<Vec<u8>>,
+        include_deleted: bool,
+    ) -> Result<Vec<VersionedEntity<T>>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let mut entities = Vec::with_capacity(keys.len());
+
+        for key in keys {
+            if let Some(bytes) = self.storage.read(&key).await.map_err(RepositoryError::Backend)? {
+                let versioned = Self::decode_envelope(&bytes)?;
+
+                if versioned.deleted && !include_deleted {
+                    continue;
+                }
+
+                entities.push(versioned);
+            }
+        }
+
+        Ok(entities)
+    }
+
+    /// Applies every operation in `transaction` atomically: either all of them commit, or, on
+    /// the first failure, every already-applied operation is reverted so the store is left
+    /// exactly as it was found.
+    ///
+    /// `transaction` is bound to this repository's entity type `T`. To stage operations across
+    /// more than one entity type, build a [`MultiTransaction`](super::transaction::MultiTransaction)
+    /// instead.
+    pub async fn apply(&self, transaction: Transaction<T>) -> Result<Vec<OperationResult<T>>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let mut results = Vec::with_capacity(transaction.operations.len());
+        let mut undo_log: Vec<(Vec<u8>, Option<Vec<u8>>)> = Vec::new();
+
+        for operation in transaction.operations {
+            match self.apply_one(operation, &mut undo_log).await {
+                Ok(result) => results.push(result),
+                Err(err) => {
+                    self.rollback(undo_log).await;
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    pub(super) async fn apply_one(
+        &self,
+        operation: Operation<T>,
+        undo_log: &mut Vec<(Vec<u8>, Option<Vec<u8>>)>,
+    ) -> Result<OperationResult<T>, RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        match operation {
+            Operation::Create(input) => {
+                let entity: T = input.into();
+                let pk = entity.get_pk();
+                let key = Self::key_for(&pk);
+
+                if let Some(existing_bytes) = self.storage.read(&key).await.map_err(RepositoryError::Backend)? {
+                    if !Self::decode_raw_envelope(&existing_bytes)?.deleted {
+                        return Err(RepositoryError::AlreadyExists(pk));
+                    }
+                }
+
+                let entity_bytes = R::encode(&entity).map_err(RepositoryError::Serialization)?;
+                let revision = Revision::initial(&entity_bytes);
+                let envelope_bytes = Self::encode_envelope(&entity, revision, Self::now_millis(), false)?;
+
+                self.snapshot(&key, undo_log).await?;
+                self.storage.write(&key, &envelope_bytes).await.map_err(RepositoryError::Backend)?;
+
+                Ok(OperationResult::Created(VersionedEntity {
+                    entity,
+                    revision,
+                    deleted: false,
+                }))
+            }
+            Operation::Update { pk, expected_revision, input } => {
+                let current = self.read_envelope(&pk).await?;
+
+                if current.revision != expected_revision {
+                    return Err(RepositoryError::Conflict);
+                }
+
+                let key = Self::key_for(&pk);
+                let entity: T = input.into();
+                let entity_bytes = R::encode(&entity).map_err(RepositoryError::Serialization)?;
+                let revision = current.revision.next(&entity_bytes);
+                let envelope_bytes = Self::encode_envelope(&entity, revision, Self::now_millis(), false)?;
+
+                self.snapshot(&key, undo_log).await?;
+                self.storage.write(&key, &envelope_bytes).await.map_err(RepositoryError::Backend)?;
+
+                Ok(OperationResult::Updated(revision))
+            }
+            Operation::Delete { pk, expected_revision } => {
+                let current = self.read_envelope(&pk).await?;
+
+                if current.revision != expected_revision {
+                    return Err(RepositoryError::Conflict);
+                }
+
+                let key = Self::key_for(&pk);
+                let entity_bytes = R::encode(&current.entity).map_err(RepositoryError::Serialization)?;
+                let revision = current.revision.next(&entity_bytes);
+                let envelope_bytes = Self::encode_envelope(&current.entity, revision, Self::now_millis(), true)?;
+
+                self.snapshot(&key, undo_log).await?;
+                self.storage.write(&key, &envelope_bytes).await.map_err(RepositoryError::Backend)?;
+
+                Ok(OperationResult::Deleted)
+            }
+        }
+    }
+
+    /// Records the bytes currently stored under `key` (or their absence) so it can be restored
+    /// by [`Repository::rollback`].
+    async fn snapshot(
+        &self,
+        key: &[u8],
+        undo_log: &mut Vec<(Vec<u8>, Option<Vec<u8>>)>,
+    ) -> Result<(), RepositoryError<T::PrimaryKey, S::ErrType, R::ErrType>> {
+        let prior = self.storage.read(key).await.map_err(RepositoryError::Backend)?;
+        undo_log.push((key.to_vec(), prior));
+
+        Ok(())
+    }
+
+    /// Restores every snapshotted key in reverse order, undoing a partially-applied transaction.
+    /// Best-effort: a backend that fails mid-transaction and then fails again on rollback leaves
+    /// the store in a partially-reverted state, which is surfaced only via the original error.
+    pub(super) async fn rollback(&self, undo_log: Vec<(Vec<u8>, Option<Vec<u8>>)>) {
+        for (key, prior) in undo_log.into_iter().rev() {
+            let _ = match prior {
+                Some(bytes) => self.storage.write(&key, &bytes).await,
+                None => self.storage.remove(&key).await,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::storage::MemoryStorage;
+    use super::super::transaction::{MultiTransaction, TransactionError};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Note {
+        id: u64,
+        body: String,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(crate = "rocket::serde")]
+    struct NoteInput {
+        id: u64,
+        body: String,
+    }
+
+    impl From<NoteInput> for Note {
+        fn from(input: NoteInput) -> Self {
+            Self {
+                id: input.id,
+                body: input.body,
+            }
+        }
+    }
+
+    impl Entity for Note {
+        type PrimaryKey = u64;
+        type Input = NoteInput;
+
+        fn get_pk(&self) -> Self::PrimaryKey {
+            self.id
+        }
+
+        const PK_DEFAULT: Self::PrimaryKey = 0;
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(crate = "rocket::serde")]
+    struct Tag {
+        id: u64,
+        label: String,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(crate = "rocket::serde")]
+    struct TagInput {
+        id: u64,
+        label: String,
+    }
+
+    impl From<TagInput> for Tag {
+        fn from(input: TagInput) -> Self {
+            Self {
+                id: input.id,
+                label: input.label,
+            }
+        }
+    }
+
+    impl Entity for Tag {
+        type PrimaryKey = u64;
+        type Input = TagInput;
+
+        fn get_pk(&self) -> Self::PrimaryKey {
+            self.id
+        }
+
+        const PK_DEFAULT: Self::PrimaryKey = 0;
+    }
+
+    fn repo() -> Repository<Note, MemoryStorage> {
+        Repository::new(MemoryStorage::default())
+    }
+
+    fn tag_repo() -> Repository<Tag, MemoryStorage> {
+        Repository::new(MemoryStorage::default())
+    }
+
+    fn note(id: u64, body: &str) -> NoteInput {
+        NoteInput { id, body: body.to_string() }
+    }
+
+    fn tag(id: u64, label: &str) -> TagInput {
+        TagInput { id, label: label.to_string() }
+    }
+
+    #[rocket::async_test]
+    async fn create_then_get_by_pk_round_trips() {
+        let repo = repo();
+        let created = repo.create(note(1, "hello")).await.unwrap();
+
+        let fetched = repo.get_by_pk(&1).await.unwrap();
+
+        assert_eq!(fetched.entity, created.entity);
+        assert_eq!(fetched.revision, created.revision);
+    }
+
+    #[rocket::async_test]
+    async fn create_over_an_existing_entity_returns_already_exists() {
+        let repo = repo();
+        repo.create(note(1, "hello")).await.unwrap();
+
+        let err = repo.create(note(1, "again")).await.unwrap_err();
+
+        assert!(matches!(err, RepositoryError::AlreadyExists(1)));
+    }
+
+    #[rocket::async_test]
+    async fn update_with_a_stale_revision_returns_conflict() {
+        let repo = repo();
+        let created = repo.create(note(1, "hello")).await.unwrap();
+        let stale = created.revision;
+
+        repo.update(&1, stale, note(1, "first edit")).await.unwrap();
+
+        let err = repo.update(&1, stale, note(1, "second edit")).await.unwrap_err();
+
+        assert!(matches!(err, RepositoryError::Conflict));
+    }
+
+    #[rocket::async_test]
+    async fn apply_rolls_back_prior_operations_on_failure() {
+        let repo = repo();
+
+        let transaction = Transaction::new()
+            .create(note(1, "first"))
+            .update(1, Revision::initial(b"a revision that was never written"), note(1, "never applied"));
+
+        let err = repo.apply(transaction).await.unwrap_err();
+
+        assert!(matches!(err, RepositoryError::Conflict));
+        assert!(matches!(repo.get_by_pk(&1).await, Err(RepositoryError::NotFound(1))));
+    }
+
+    #[rocket::async_test]
+    async fn multi_transaction_commits_across_entity_types() {
+        let notes = repo();
+        let tags = tag_repo();
+
+        MultiTransaction::new()
+            .stage(&notes, Operation::Create(note(1, "hello")))
+            .stage(&tags, Operation::Create(tag(1, "greeting")))
+            .apply()
+            .await
+            .unwrap();
+
+        assert_eq!(notes.get_by_pk(&1).await.unwrap().entity.body, "hello");
+        assert_eq!(tags.get_by_pk(&1).await.unwrap().entity.label, "greeting");
+    }
+
+    #[rocket::async_test]
+    async fn multi_transaction_rolls_back_every_entity_type_on_failure() {
+        let notes = repo();
+        let tags = tag_repo();
+
+        notes.create(note(1, "original")).await.unwrap();
+
+        let err = MultiTransaction::new()
+            .stage(&tags, Operation::Create(tag(1, "never applied")))
+            .stage(
+                &notes,
+                Operation::Update {
+                    pk: 1,
+                    expected_revision: Revision::initial(b"a revision that was never written"),
+                    input: note(1, "never applied either"),
+                },
+            )
+            .apply()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, TransactionError::Conflict));
+        assert_eq!(notes.get_by_pk(&1).await.unwrap().entity.body, "original");
+        assert!(matches!(tags.get_by_pk(&1).await, Err(RepositoryError::NotFound(1))));
+    }
+
+    #[rocket::async_test]
+    async fn get_page_does_not_shrink_below_limit_when_tombstones_fall_inside_the_window() {
+        let repo = repo();
+
+        for id in 1..=5 {
+            repo.create(note(id, "note")).await.unwrap();
+        }
+
+        // Tombstone every odd-numbered note so a naive scan-then-truncate would leave this page
+        // short even though four live notes (2, 4, 6 and 7) remain once 6 and 7 are added.
+        for id in [1, 3, 5] {
+            let created = repo.get_by_pk(&id).await.unwrap();
+            repo.delete(&id, created.revision).await.unwrap();
+        }
+
+        repo.create(note(6, "note")).await.unwrap();
+        repo.create(note(7, "note")).await.unwrap();
+
+        let page = repo.get_page(None, 3, false).await.unwrap();
+
+        assert_eq!(page.len(), 3);
+        assert_eq!(page.iter().map(|e| e.entity.id).collect::<Vec<_>>(), vec![2, 4, 6]);
+    }
+
+    #[rocket::async_test]
+    async fn get_range_returns_only_keys_within_the_given_bounds() {
+        let repo = repo();
+
+        for id in 1..=5 {
+            repo.create(note(id, "note")).await.unwrap();
+        }
+
+        let entities = repo.get_range(Some(&2), Some(&4), None, false).await.unwrap();
+
+        assert_eq!(entities.iter().map(|e| e.entity.id).collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[rocket::async_test]
+    async fn get_by_prefix_matches_only_keys_sharing_the_prefix() {
+        let repo = repo();
+
+        for id in [1, 2, 3, 256] {
+            repo.create(note(id, "note")).await.unwrap();
+        }
+
+        // u64 keys encode as 8 big-endian bytes; 1..=3 start with 7 zero bytes while 256 starts
+        // with only 6, so a 7-byte all-zero prefix matches the former but not the latter.
+        let entities = repo.get_by_prefix(vec![0x00; 7], None, false).await.unwrap();
+
+        assert_eq!(entities.iter().map(|e| e.entity.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[rocket::async_test]
+    async fn merge_keeps_the_newer_write_across_repositories() {
+        let local = repo();
+        let remote = repo();
+
+        local.create(note(1, "from local")).await.unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        remote.create(note(1, "from remote")).await.unwrap();
+
+        local.merge(&remote).await.unwrap();
+
+        assert_eq!(local.get_by_pk(&1).await.unwrap().entity.body, "from remote");
+    }
+
+    #[rocket::async_test]
+    async fn merge_propagates_tombstones_from_other_repository() {
+        let local = repo();
+        let remote = repo();
+
+        local.create(note(1, "shared")).await.unwrap();
+        remote.merge(&local).await.unwrap();
+
+        let replicated = remote.get_by_pk(&1).await.unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        remote.delete(&1, replicated.revision).await.unwrap();
+
+        local.merge(&remote).await.unwrap();
+
+        assert!(matches!(local.get_by_pk(&1).await, Err(RepositoryError::NotFound(1))));
+    }
+
+    #[test]
+    fn merge_breaks_timestamp_ties_in_favor_of_the_tombstone() {
+        let live = Envelope {
+            revision: Revision::initial(b"live"),
+            timestamp: 1_000,
+            deleted: false,
+            entity: Note { id: 1, body: "live".to_string() },
+        };
+        let tombstoned = Envelope {
+            revision: Revision::initial(b"tombstoned"),
+            timestamp: 1_000,
+            deleted: true,
+            entity: Note { id: 1, body: "live".to_string() },
+        };
+
+        assert!(Repository::<Note, MemoryStorage>::wins(&tombstoned, &live));
+        assert!(!Repository::<Note, MemoryStorage>::wins(&live, &tombstoned));
+    }
+}
@@ -0,0 +1,32 @@
+use std::error::Error;
+
+use rocket::serde::json::serde_json;
+use rocket::serde::{Deserialize, Serialize};
+
+/// Converts entities to and from their on-disk byte representation. Primary keys are not
+/// encoded through this trait; storage keys come from [`KeyEncoding`](super::key::KeyEncoding).
+pub trait Serializer {
+    /// The type of error returned when encoding or decoding fails.
+    type ErrType: Error;
+
+    /// Encodes a value to its byte representation.
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, Self::ErrType>;
+
+    /// Decodes a value from its byte representation.
+    fn decode<T: for<'a> Deserialize<'a>>(bytes: &[u8]) -> Result<T, Self::ErrType>;
+}
+
+/// The default [`Serializer`], backed by `rocket::serde`'s JSON support.
+pub struct JsonSerializer;
+
+impl Serializer for JsonSerializer {
+    type ErrType = serde_json::Error;
+
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, Self::ErrType> {
+        serde_json::to_vec(value)
+    }
+
+    fn decode<T: for<'a> Deserialize<'a>>(bytes: &[u8]) -> Result<T, Self::ErrType> {
+        serde_json::from_slice(bytes)
+    }
+}
@@ -0,0 +1,167 @@
+use std::io;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use rocket::tokio::fs;
+
+use super::Storage;
+
+/// A [`Storage`] implementation that persists each entry as its own file in a directory,
+/// with keys hex-encoded to form valid file names.
+pub struct FsStorage {
+    root: PathBuf,
+}
+
+impl FsStorage {
+    /// Creates a storage rooted at `root`. The directory is created lazily on first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path_for(&self, key: &[u8]) -> PathBuf {
+        self.root.join(encode_hex(key))
+    }
+}
+
+#[async_trait]
+impl Storage for FsStorage {
+    type ErrType = io::Error;
+
+    async fn read(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::ErrType> {
+        match fs::read(self.path_for(key)).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn write(&self, key: &[u8], value: &[u8]) -> Result<(), Self::ErrType> {
+        fs::create_dir_all(&self.root).await?;
+        fs::write(self.path_for(key), value).await
+    }
+
+    async fn remove(&self, key: &[u8]) -> Result<(), Self::ErrType> {
+        match fs::remove_file(self.path_for(key)).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn list_keys(&self) -> Result<Vec<Vec<u8>>, Self::ErrType> {
+        let mut entries = match fs::read_dir(&self.root).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut keys = Vec::new();
+
+        while let Some(entry) = entries.next_entry().await? {
+            if let Some(name) = entry.file_name().to_str().and_then(decode_hex) {
+                keys.push(name);
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+/// Encodes `bytes` as a lowercase hex string suitable for use as a file name.
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Decodes a lowercase hex string back into its original bytes.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    /// A directory under the system temp dir, unique to this test process and call site, removed
+    /// when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+            let path = std::env::temp_dir().join(format!(
+                "rust-rocket-adventures-fs-storage-test-{}-{}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::SeqCst)
+            ));
+
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[rocket::async_test]
+    async fn write_then_read_round_trips() {
+        let dir = TempDir::new();
+        let storage = FsStorage::new(dir.0.clone());
+
+        storage.write(b"key", b"value").await.unwrap();
+
+        assert_eq!(storage.read(b"key").await.unwrap(), Some(b"value".to_vec()));
+    }
+
+    #[rocket::async_test]
+    async fn read_of_a_missing_key_returns_none() {
+        let dir = TempDir::new();
+        let storage = FsStorage::new(dir.0.clone());
+
+        assert_eq!(storage.read(b"missing").await.unwrap(), None);
+    }
+
+    #[rocket::async_test]
+    async fn remove_deletes_a_written_key() {
+        let dir = TempDir::new();
+        let storage = FsStorage::new(dir.0.clone());
+
+        storage.write(b"key", b"value").await.unwrap();
+        storage.remove(b"key").await.unwrap();
+
+        assert_eq!(storage.read(b"key").await.unwrap(), None);
+    }
+
+    #[rocket::async_test]
+    async fn list_keys_returns_every_written_key() {
+        let dir = TempDir::new();
+        let storage = FsStorage::new(dir.0.clone());
+
+        storage.write(b"one", b"1").await.unwrap();
+        storage.write(b"two", b"2").await.unwrap();
+
+        let mut keys = storage.list_keys().await.unwrap();
+        keys.sort();
+
+        assert_eq!(keys, vec![b"one".to_vec(), b"two".to_vec()]);
+    }
+
+    #[rocket::async_test]
+    async fn list_keys_on_a_nonexistent_directory_is_empty() {
+        let dir = TempDir::new();
+        let storage = FsStorage::new(dir.0.clone());
+
+        assert_eq!(storage.list_keys().await.unwrap(), Vec::<Vec<u8>>::new());
+    }
+}
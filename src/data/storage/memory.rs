@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+
+use super::Storage;
+
+/// An in-memory [`Storage`] backed by a [`HashMap`], useful for tests and ephemeral data.
+#[derive(Default)]
+pub struct MemoryStorage {
+    entries: RwLock<HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    /// Creates a new, empty in-memory storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    type ErrType = Infallible;
+
+    async fn read(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::ErrType> {
+        Ok(self.entries.read().unwrap().get(key).cloned())
+    }
+
+    async fn write(&self, key: &[u8], value: &[u8]) -> Result<(), Self::ErrType> {
+        self.entries.write().unwrap().insert(key.to_vec(), value.to_vec());
+
+        Ok(())
+    }
+
+    async fn remove(&self, key: &[u8]) -> Result<(), Self::ErrType> {
+        self.entries.write().unwrap().remove(key);
+
+        Ok(())
+    }
+
+    async fn list_keys(&self) -> Result<Vec<Vec<u8>>, Self::ErrType> {
+        Ok(self.entries.read().unwrap().keys().cloned().collect())
+    }
+}
@@ -0,0 +1,56 @@
+mod fs;
+mod memory;
+
+pub use fs::FsStorage;
+pub use memory::MemoryStorage;
+
+use std::error::Error;
+
+use async_trait::async_trait;
+
+/// A backing store abstraction over byte-level key/value storage, keyed by an entity's
+/// encoded primary key.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// The type of error returned when a storage operation fails.
+    type ErrType: Error + Send + Sync + 'static;
+
+    /// Reads the bytes stored under `key`, if any.
+    async fn read(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::ErrType>;
+
+    /// Writes `value` under `key`, overwriting any existing entry.
+    async fn write(&self, key: &[u8], value: &[u8]) -> Result<(), Self::ErrType>;
+
+    /// Removes the entry stored under `key`, if any. Removing a missing key is not an error.
+    async fn remove(&self, key: &[u8]) -> Result<(), Self::ErrType>;
+
+    /// Lists every key currently present in this storage.
+    async fn list_keys(&self) -> Result<Vec<Vec<u8>>, Self::ErrType>;
+
+    /// Scans keys in ascending lexicographic order within `[start, end)` (`end` exclusive),
+    /// returning at most `limit` keys if given.
+    ///
+    /// The default implementation lists every key and filters/sorts it in memory; backends with
+    /// a natively ordered key space should override this for efficiency.
+    async fn scan_keys(
+        &self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Vec<u8>>, Self::ErrType> {
+        let mut keys = self.list_keys().await?;
+        keys.sort();
+
+        let mut keys: Vec<Vec<u8>> = keys
+            .into_iter()
+            .filter(|key| start.map_or(true, |start| key.as_slice() >= start))
+            .filter(|key| end.map_or(true, |end| key.as_slice() < end))
+            .collect();
+
+        if let Some(limit) = limit {
+            keys.truncate(limit);
+        }
+
+        Ok(keys)
+    }
+}
@@ -0,0 +1,261 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use rocket::serde::{Deserialize, Serialize};
+
+use super::entity::{Entity, Revision, VersionedEntity};
+use super::error::RepositoryError;
+use super::repository::Repository;
+use super::serializer::Serializer;
+use super::storage::Storage;
+
+/// A single mutation to apply as part of a [`Transaction`], or stage into a [`MultiTransaction`].
+pub enum Operation<T: Entity> {
+    /// Create a new entity from the given input.
+    Create(T::Input),
+    /// Replace the entity at `pk` with the given input, provided it is still at
+    /// `expected_revision`.
+    Update {
+        pk: T::PrimaryKey,
+        expected_revision: Revision,
+        input: T::Input,
+    },
+    /// Remove the entity at `pk`, provided it is still at `expected_revision`.
+    Delete { pk: T::PrimaryKey, expected_revision: Revision },
+}
+
+/// The outcome of a single [`Operation`] applied as part of a transaction.
+pub enum OperationResult<T: Entity> {
+    /// The entity created by a [`Operation::Create`].
+    Created(VersionedEntity<T>),
+    /// An [`Operation::Update`] was applied, yielding this new revision.
+    Updated(Revision),
+    /// An [`Operation::Delete`] was applied.
+    Deleted,
+}
+
+/// An ordered list of [`Operation`]s to apply atomically against a single
+/// [`Repository`](crate::data::repository::Repository): either every operation commits, or the
+/// store is left exactly as it was found.
+///
+/// Scoped to one entity type `T`. To stage operations across more than one entity type, use
+/// [`MultiTransaction`] instead.
+pub struct Transaction<T: Entity> {
+    pub(super) operations: Vec<Operation<T>>,
+}
+
+impl<T: Entity> Transaction<T> {
+    /// Starts a new, empty transaction.
+    pub fn new() -> Self {
+        Self { operations: Vec::new() }
+    }
+
+    /// Appends a create operation.
+    pub fn create(mut self, input: T::Input) -> Self {
+        self.operations.push(Operation::Create(input));
+        self
+    }
+
+    /// Appends an update operation, applied only if `pk` is still at `expected_revision`.
+    pub fn update(mut self, pk: T::PrimaryKey, expected_revision: Revision, input: T::Input) -> Self {
+        self.operations.push(Operation::Update {
+            pk,
+            expected_revision,
+            input,
+        });
+        self
+    }
+
+    /// Appends a delete operation, applied only if `pk` is still at `expected_revision`.
+    pub fn delete(mut self, pk: T::PrimaryKey, expected_revision: Revision) -> Self {
+        self.operations.push(Operation::Delete { pk, expected_revision });
+        self
+    }
+}
+
+impl<T: Entity> Default for Transaction<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Errors that can occur while applying a [`MultiTransaction`].
+///
+/// Unlike [`RepositoryError`], this is erased of any single entity type's primary key and
+/// serialization/backend error types, since a `MultiTransaction` can stage operations against
+/// repositories of different entity types, and therefore different [`Serializer`]/[`Storage`]
+/// error types.
+#[derive(Debug)]
+pub enum TransactionError {
+    /// No entity existed for a staged [`Operation::Update`] or [`Operation::Delete`]'s primary key.
+    NotFound,
+    /// An entity already existed where a staged [`Operation::Create`] expected none.
+    AlreadyExists,
+    /// A staged operation's expected revision did not match the entity's stored revision.
+    Conflict,
+    /// Encoding or decoding an entity failed.
+    Serialization(Box<dyn Error + Send + Sync>),
+    /// The underlying storage backend failed.
+    Backend(Box<dyn Error + Send + Sync>),
+}
+
+impl Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no entity found for a staged operation's primary key"),
+            Self::AlreadyExists => write!(f, "an entity already exists for a staged operation's primary key"),
+            Self::Conflict => write!(f, "entity revision does not match the expected revision"),
+            Self::Serialization(err) => write!(f, "serialization error: {err}"),
+            Self::Backend(err) => write!(f, "storage error: {err}"),
+        }
+    }
+}
+
+impl Error for TransactionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NotFound | Self::AlreadyExists | Self::Conflict => None,
+            Self::Serialization(err) => Some(err.as_ref()),
+            Self::Backend(err) => Some(err.as_ref()),
+        }
+    }
+}
+
+fn into_transaction_error<PK, SErr, DErr>(err: RepositoryError<PK, SErr, DErr>) -> TransactionError
+where
+    SErr: Error + Send + Sync + 'static,
+    DErr: Error + Send + Sync + 'static,
+{
+    match err {
+        RepositoryError::NotFound(_) => TransactionError::NotFound,
+        RepositoryError::AlreadyExists(_) => TransactionError::AlreadyExists,
+        RepositoryError::Conflict => TransactionError::Conflict,
+        RepositoryError::Serialization(err) => TransactionError::Serialization(Box::new(err)),
+        RepositoryError::Backend(err) => TransactionError::Backend(Box::new(err)),
+    }
+}
+
+/// An [`Operation`] staged against a specific [`Repository`], type-erased so a
+/// [`MultiTransaction`] can hold staged operations against more than one entity type at once.
+#[async_trait]
+pub(super) trait StagedOperation: Send + Sync {
+    /// Applies the staged operation, recording what it overwrote so [`StagedOperation::rollback`]
+    /// can undo it later.
+    async fn apply(&self) -> Result<(), TransactionError>;
+
+    /// Restores whatever the last call to `apply` overwrote. A no-op if `apply` was never called,
+    /// or failed before writing anything.
+    async fn rollback(&self);
+}
+
+struct RepositoryStagedOperation<'a, T, S, R>
+where
+    T: Entity,
+    S: Storage,
+{
+    repository: &'a Repository<T, S, R>,
+    operation: Mutex<Option<Operation<T>>>,
+    undo_log: Mutex<Option<Vec<(Vec<u8>, Option<Vec<u8>>)>>>,
+}
+
+#[async_trait]
+impl<'a, T, S, R> StagedOperation for RepositoryStagedOperation<'a, T, S, R>
+where
+    T: Entity + Serialize + for<'de> Deserialize<'de> + Send + Sync,
+    T::Input: Send,
+    T::PrimaryKey: Send,
+    S: Storage,
+    R: Serializer + Send + Sync,
+    R::ErrType: Send + Sync + 'static,
+{
+    async fn apply(&self) -> Result<(), TransactionError> {
+        let operation = self
+            .operation
+            .lock()
+            .unwrap()
+            .take()
+            .expect("a staged operation must not be applied more than once");
+
+        let mut undo_log = Vec::new();
+        let result = self.repository.apply_one(operation, &mut undo_log).await;
+        *self.undo_log.lock().unwrap() = Some(undo_log);
+
+        result.map(|_| ()).map_err(into_transaction_error)
+    }
+
+    async fn rollback(&self) {
+        let undo_log = self.undo_log.lock().unwrap().take();
+
+        if let Some(undo_log) = undo_log {
+            self.repository.rollback(undo_log).await;
+        }
+    }
+}
+
+/// An ordered list of [`Operation`]s, each staged against its own [`Repository`] via
+/// [`MultiTransaction::stage`], applied atomically across all of them: either every operation
+/// commits, or every repository it touched is left exactly as it was found.
+///
+/// Where [`Transaction`] is scoped to a single entity type, a `MultiTransaction` can span entity
+/// types - and [`Storage`]/[`Serializer`] backends - freely, since each staged operation carries
+/// its own repository reference rather than sharing one typed repository for the whole batch.
+pub struct MultiTransaction<'a> {
+    operations: Vec<Box<dyn StagedOperation + 'a>>,
+}
+
+impl<'a> MultiTransaction<'a> {
+    /// Starts a new, empty transaction.
+    pub fn new() -> Self {
+        Self { operations: Vec::new() }
+    }
+
+    /// Stages `operation` against `repository`, to be applied alongside every other operation
+    /// staged into this transaction when [`MultiTransaction::apply`] is called.
+    pub fn stage<T, S, R>(mut self, repository: &'a Repository<T, S, R>, operation: Operation<T>) -> Self
+    where
+        T: Entity + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'a,
+        T::Input: Send + 'a,
+        T::PrimaryKey: Send + 'a,
+        S: Storage,
+        R: Serializer + Send + Sync + 'a,
+        R::ErrType: Send + Sync + 'static,
+    {
+        self.operations.push(Box::new(RepositoryStagedOperation {
+            repository,
+            operation: Mutex::new(Some(operation)),
+            undo_log: Mutex::new(None),
+        }));
+        self
+    }
+
+    /// Applies every staged operation in order. If any operation fails, every operation applied
+    /// so far - including ones staged against a different entity type's repository - is rolled
+    /// back in reverse order, so the transaction never leaves only some of the repositories it
+    /// touched mutated.
+    pub async fn apply(self) -> Result<(), TransactionError> {
+        let mut applied = Vec::with_capacity(self.operations.len());
+
+        for operation in self.operations {
+            match operation.apply().await {
+                Ok(()) => applied.push(operation),
+                Err(err) => {
+                    for operation in applied.into_iter().rev() {
+                        operation.rollback().await;
+                    }
+
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Default for MultiTransaction<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}